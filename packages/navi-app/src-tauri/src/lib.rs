@@ -1,12 +1,46 @@
-use tauri::{Manager, WebviewUrl, WebviewWindowBuilder};
+use tauri::{Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
 use tauri_plugin_shell::ShellExt;
-use std::sync::Mutex;
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+// Label of the window created by Tauri at startup (see `tauri.conf.json`).
+const MAIN_WINDOW_LABEL: &str = "main";
 
 const BUNDLED_SERVER_PORT: u16 = 3011;
 const BUNDLED_PTY_PORT: u16 = 3012;
 
-struct SidecarState(Mutex<Option<tauri_plugin_shell::process::CommandChild>>);
+// Backoff schedule for the sidecar supervisor: doubles each attempt, capped at 30s.
+const SIDECAR_BACKOFF_INITIAL_MS: u64 = 500;
+const SIDECAR_BACKOFF_MAX_MS: u64 = 30_000;
+// A sidecar that survives this long is considered healthy again; resets the backoff/failure count.
+const SIDECAR_STABLE_AFTER_SECS: u64 = 10;
+// Consecutive immediate failures before the supervisor gives up and emits `failed`.
+const SIDECAR_MAX_CONSECUTIVE_FAILURES: u32 = 6;
+
+// Roll the log once it crosses this size, keeping this many rotated backups.
+const SIDECAR_LOG_MAX_BYTES: u64 = 5 * 1024 * 1024;
+const SIDECAR_LOG_MAX_BACKUPS: u32 = 3;
+
+// One sidecar per window, rather than one shared backend for the whole app.
+struct WindowSidecar {
+    child: Mutex<Option<tauri_plugin_shell::process::CommandChild>>,
+    // Bumped when the window closes; see `supervise_window_sidecar`.
+    generation: AtomicU32,
+    ports: ServerPorts,
+    // Current log file size, so `append_sidecar_log` doesn't need to `stat` per line.
+    log_size: AtomicU64,
+}
+
+// Keyed by window label. Entries are removed as their windows close.
+struct SidecarRegistry(Mutex<HashMap<String, Arc<WindowSidecar>>>);
+
+#[derive(Clone, Copy)]
 struct ServerPorts {
     server: u16,
     pty: u16,
@@ -15,14 +49,408 @@ struct ServerPorts {
 // Counter for unique window labels
 static WINDOW_COUNTER: AtomicU32 = AtomicU32::new(1);
 
+const WINDOW_PLACEMENTS_FILE_NAME: &str = "window-placements.json";
+
+// Persisted position/size/maximized state for a project window, keyed by project
+// id (not window label, which is only stable for the lifetime of one run).
+#[derive(Clone, Copy, serde::Serialize, serde::Deserialize)]
+struct WindowPlacement {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    maximized: bool,
+}
+
+struct WindowPlacementStore(Mutex<HashMap<String, WindowPlacement>>);
+
+fn window_placements_path(app: &tauri::AppHandle) -> Option<PathBuf> {
+    app.path().app_data_dir().ok().map(|dir| dir.join(WINDOW_PLACEMENTS_FILE_NAME))
+}
+
+fn load_window_placements(app: &tauri::AppHandle) -> HashMap<String, WindowPlacement> {
+    window_placements_path(app)
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn save_window_placements(app: &tauri::AppHandle, placements: &HashMap<String, WindowPlacement>) {
+    let Some(path) = window_placements_path(app) else { return };
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string_pretty(placements) {
+        let _ = std::fs::write(path, json);
+    }
+}
+
+fn snapshot_window_placement(window: &tauri::WebviewWindow) -> Option<WindowPlacement> {
+    let maximized = window.is_maximized().unwrap_or(false);
+    let position = window.outer_position().ok()?;
+    let size = window.outer_size().ok()?;
+    Some(WindowPlacement { x: position.x, y: position.y, width: size.width, height: size.height, maximized })
+}
+
+// Rejects placements whose origin isn't on any currently connected monitor.
+fn placement_fits_a_monitor(app: &tauri::AppHandle, placement: &WindowPlacement) -> bool {
+    let Ok(monitors) = app.available_monitors() else { return false };
+    monitors.iter().any(|monitor| {
+        let pos = monitor.position();
+        let size = monitor.size();
+        placement.x >= pos.x
+            && placement.x < pos.x + size.width as i32
+            && placement.y >= pos.y
+            && placement.y < pos.y + size.height as i32
+    })
+}
+
+fn persist_window_placement(app: &tauri::AppHandle, project_id: &str, window: &tauri::WebviewWindow) {
+    let Some(placement) = snapshot_window_placement(window) else { return };
+    let store = app.state::<WindowPlacementStore>();
+    let mut placements = store.0.lock().unwrap();
+    placements.insert(project_id.to_string(), placement);
+    save_window_placements(app, &placements);
+}
+
+// Binds `preferred` if it's free, otherwise falls back to an OS-assigned ephemeral
+// port. Either way the listener is dropped immediately so the sidecar can bind it.
+fn find_free_port(preferred: u16) -> u16 {
+    if let Ok(listener) = TcpListener::bind(("127.0.0.1", preferred)) {
+        return listener.local_addr().map(|addr| addr.port()).unwrap_or(preferred);
+    }
+
+    TcpListener::bind(("127.0.0.1", 0))
+        .and_then(|listener| listener.local_addr())
+        .map(|addr| addr.port())
+        .unwrap_or(preferred)
+}
+
+#[derive(Clone, serde::Serialize)]
+struct SidecarStatusPayload {
+    window_label: String,
+    status: &'static str,
+}
+
+fn emit_sidecar_status(app: &tauri::AppHandle, window_label: &str, status: &'static str) {
+    let _ = app.emit_to(window_label, "sidecar-status", SidecarStatusPayload {
+        window_label: window_label.to_string(),
+        status,
+    });
+}
+
+// Each window gets its own log file so output stays attributable.
+fn sidecar_log_file_name(window_label: &str) -> String {
+    format!("navi-server-{window_label}.log")
+}
+
+fn sidecar_log_path(log_dir: &Path, window_label: &str) -> PathBuf {
+    log_dir.join(sidecar_log_file_name(window_label))
+}
+
+// Rolls `navi-server-<label>.log` -> `.log.1` -> ... -> `.N`, dropping the oldest.
+fn rotate_sidecar_log(log_dir: &Path, window_label: &str) {
+    let file_name = sidecar_log_file_name(window_label);
+    for i in (1..SIDECAR_LOG_MAX_BACKUPS).rev() {
+        let from = log_dir.join(format!("{file_name}.{i}"));
+        let to = log_dir.join(format!("{file_name}.{}", i + 1));
+        if from.exists() {
+            let _ = std::fs::rename(from, to);
+        }
+    }
+    let _ = std::fs::rename(sidecar_log_path(log_dir, window_label), log_dir.join(format!("{file_name}.1")));
+}
+
+fn unix_timestamp_millis() -> u128 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis()).unwrap_or(0)
+}
+
+// Tees a single sidecar output line to that window's rotating log file.
+fn append_sidecar_log(log_dir: &Path, window_label: &str, entry: &WindowSidecar, stream: &str, line: &str) {
+    let path = sidecar_log_path(log_dir, window_label);
+
+    if entry.log_size.load(Ordering::Relaxed) >= SIDECAR_LOG_MAX_BYTES {
+        rotate_sidecar_log(log_dir, window_label);
+        entry.log_size.store(0, Ordering::Relaxed);
+    }
+
+    let entry_line = format!("[{}] [{}] {}\n", unix_timestamp_millis(), stream, line);
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(&path) {
+        if file.write_all(entry_line.as_bytes()).is_ok() {
+            entry.log_size.fetch_add(entry_line.len() as u64, Ordering::Relaxed);
+        }
+    }
+}
+
+fn build_sidecar_command(
+    app: &tauri::AppHandle,
+    ports: &ServerPorts,
+    project_id: Option<&str>,
+) -> tauri_plugin_shell::process::Command {
+    let mut sidecar_command = app.shell().sidecar("navi-server").unwrap()
+        .args([ports.server.to_string()])
+        .env("NAVI_PTY_PORT", ports.pty.to_string());
+
+    if let Some(project_id) = project_id {
+        sidecar_command = sidecar_command.env("NAVI_PROJECT_ID", project_id);
+    }
+
+    if let Ok(log_dir) = app.path().app_log_dir() {
+        sidecar_command = sidecar_command.env("NAVI_LOG_DIR", log_dir.to_string_lossy().to_string());
+    }
+
+    if let Ok(resource_dir) = app.path().resource_dir() {
+        sidecar_command = sidecar_command.env("TAURI_RESOURCE_DIR", resource_dir.to_string_lossy().to_string());
+        let cli_path = resource_dir.join("resources").join("claude-agent-sdk").join("cli.js");
+        sidecar_command = sidecar_command.env("NAVI_CLAUDE_CODE_PATH", cli_path.to_string_lossy().to_string());
+
+        if let Some(contents_dir) = resource_dir.parent() {
+            let bun_path = contents_dir.join("MacOS").join("bun");
+            sidecar_command = sidecar_command.env("NAVI_BUN_PATH", bun_path.to_string_lossy().to_string());
+        }
+    }
+
+    sidecar_command
+}
+
+type SidecarSpawnResult = tauri_plugin_shell::Result<(
+    tokio::sync::mpsc::Receiver<tauri_plugin_shell::process::CommandEvent>,
+    tauri_plugin_shell::process::CommandChild,
+)>;
+
+// Serializes port allocation + first spawn, so two windows opened back-to-back
+// can't both be handed the same "free" port before either sidecar binds it.
+static SIDECAR_SPAWN_LOCK: Mutex<()> = Mutex::new(());
+
+// Allocates ports, registers this window's `WindowSidecar` entry, and hands off
+// to the supervisor loop. Returns the ports for the frontend.
+fn spawn_window_sidecar(app: &tauri::AppHandle, window_label: String, project_id: Option<String>) -> ServerPorts {
+    let spawn_guard = SIDECAR_SPAWN_LOCK.lock().unwrap();
+
+    // Ports already handed to another window may not be bound by its sidecar yet,
+    // so a successful probe alone doesn't prove they're free.
+    let handed_out: Vec<u16> = app.state::<SidecarRegistry>().0.lock().unwrap()
+        .values()
+        .flat_map(|entry| [entry.ports.server, entry.ports.pty])
+        .collect();
+    let allocate = |preferred: u16| {
+        let port = find_free_port(preferred);
+        if handed_out.contains(&port) { find_free_port(0) } else { port }
+    };
+
+    let ports = ServerPorts {
+        server: allocate(BUNDLED_SERVER_PORT),
+        pty: allocate(BUNDLED_PTY_PORT),
+    };
+
+    // One-off stat to seed size from a log left over from an earlier run.
+    let initial_log_size = app
+        .path()
+        .app_log_dir()
+        .ok()
+        .and_then(|dir| std::fs::metadata(sidecar_log_path(&dir, &window_label)).ok())
+        .map(|metadata| metadata.len())
+        .unwrap_or(0);
+
+    let entry = Arc::new(WindowSidecar {
+        child: Mutex::new(None),
+        generation: AtomicU32::new(0),
+        ports,
+        log_size: AtomicU64::new(initial_log_size),
+    });
+
+    app.state::<SidecarRegistry>().0.lock().unwrap().insert(window_label.clone(), entry.clone());
+
+    let first_attempt = build_sidecar_command(app, &ports, project_id.as_deref()).spawn();
+    drop(spawn_guard);
+
+    supervise_window_sidecar(app.clone(), window_label, project_id, entry, first_attempt);
+
+    ports
+}
+
+// Supervises `navi-server` for one window's lifetime, respawning with backoff on
+// unexpected exit and stopping once `entry`'s generation is bumped by window close.
+fn supervise_window_sidecar(
+    app: tauri::AppHandle,
+    window_label: String,
+    project_id: Option<String>,
+    entry: Arc<WindowSidecar>,
+    first_attempt: SidecarSpawnResult,
+) {
+    let generation = entry.generation.load(Ordering::SeqCst);
+
+    tauri::async_runtime::spawn(async move {
+        let mut backoff_ms = SIDECAR_BACKOFF_INITIAL_MS;
+        let mut consecutive_failures: u32 = 0;
+        let log_dir = app.path().app_log_dir().ok();
+        let mut pending_attempt = Some(first_attempt);
+
+        loop {
+            if entry.generation.load(Ordering::SeqCst) != generation {
+                return;
+            }
+
+            emit_sidecar_status(&app, &window_label, "starting");
+
+            let attempt = pending_attempt
+                .take()
+                .unwrap_or_else(|| build_sidecar_command(&app, &entry.ports, project_id.as_deref()).spawn());
+
+            let (mut rx, child) = match attempt {
+                Ok(pair) => pair,
+                Err(err) => {
+                    eprintln!("[sidecar:{window_label}] failed to spawn navi-server: {err}");
+                    consecutive_failures += 1;
+                    if consecutive_failures >= SIDECAR_MAX_CONSECUTIVE_FAILURES {
+                        emit_sidecar_status(&app, &window_label, "failed");
+                        return;
+                    }
+                    emit_sidecar_status(&app, &window_label, "restarting");
+                    tokio::time::sleep(Duration::from_millis(backoff_ms)).await;
+                    backoff_ms = (backoff_ms * 2).min(SIDECAR_BACKOFF_MAX_MS);
+                    continue;
+                }
+            };
+
+            if entry.generation.load(Ordering::SeqCst) != generation {
+                let _ = child.kill();
+                return;
+            }
+            entry.child.lock().unwrap().replace(child);
+
+            emit_sidecar_status(&app, &window_label, "ready");
+            let started_at = Instant::now();
+
+            while let Some(event) = rx.recv().await {
+                match event {
+                    tauri_plugin_shell::process::CommandEvent::Stdout(line) => {
+                        let line = String::from_utf8_lossy(&line).to_string();
+                        println!("[sidecar:{window_label} stdout] {line}");
+                        if let Some(dir) = &log_dir {
+                            append_sidecar_log(dir, &window_label, &entry, "stdout", &line);
+                        }
+                    }
+                    tauri_plugin_shell::process::CommandEvent::Stderr(line) => {
+                        let line = String::from_utf8_lossy(&line).to_string();
+                        eprintln!("[sidecar:{window_label} stderr] {line}");
+                        if let Some(dir) = &log_dir {
+                            append_sidecar_log(dir, &window_label, &entry, "stderr", &line);
+                        }
+                    }
+                    tauri_plugin_shell::process::CommandEvent::Terminated(payload) => {
+                        println!("[sidecar:{window_label}] navi-server terminated: {:?}", payload);
+                        emit_sidecar_status(&app, &window_label, "crashed");
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+
+            entry.child.lock().unwrap().take();
+
+            if entry.generation.load(Ordering::SeqCst) != generation {
+                return;
+            }
+
+            if started_at.elapsed() >= Duration::from_secs(SIDECAR_STABLE_AFTER_SECS) {
+                backoff_ms = SIDECAR_BACKOFF_INITIAL_MS;
+                consecutive_failures = 0;
+            } else {
+                consecutive_failures += 1;
+                if consecutive_failures >= SIDECAR_MAX_CONSECUTIVE_FAILURES {
+                    emit_sidecar_status(&app, &window_label, "failed");
+                    return;
+                }
+            }
+
+            emit_sidecar_status(&app, &window_label, "restarting");
+            tokio::time::sleep(Duration::from_millis(backoff_ms)).await;
+            backoff_ms = (backoff_ms * 2).min(SIDECAR_BACKOFF_MAX_MS);
+        }
+    });
+}
+
 #[tauri::command]
 fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
 }
 
 #[tauri::command]
-fn get_server_ports(state: tauri::State<ServerPorts>) -> (u16, u16) {
-    (state.server, state.pty)
+fn get_server_ports(window: tauri::WebviewWindow, registry: tauri::State<SidecarRegistry>) -> Result<(u16, u16), String> {
+    registry.0.lock().unwrap()
+        .get(window.label())
+        .map(|entry| (entry.ports.server, entry.ports.pty))
+        .ok_or_else(|| format!("no sidecar registered for window '{}'", window.label()))
+}
+
+// Scoped to the calling window's own sidecar log.
+#[tauri::command]
+fn get_log_path(window: tauri::WebviewWindow) -> Result<String, String> {
+    let log_dir = window.path().app_log_dir().map_err(|e| e.to_string())?;
+    Ok(sidecar_log_path(&log_dir, window.label()).to_string_lossy().to_string())
+}
+
+#[derive(Clone, serde::Serialize)]
+struct UpdateMetadata {
+    version: String,
+    notes: Option<String>,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct UpdateProgressPayload {
+    downloaded: u64,
+    total: Option<u64>,
+}
+
+#[cfg(desktop)]
+#[tauri::command]
+async fn check_for_update(app: tauri::AppHandle) -> Result<Option<UpdateMetadata>, String> {
+    use tauri_plugin_updater::UpdaterExt;
+
+    let update = app.updater().map_err(|e| e.to_string())?
+        .check().await
+        .map_err(|e| e.to_string())?;
+
+    Ok(update.map(|update| UpdateMetadata { version: update.version, notes: update.body }))
+}
+
+#[cfg(desktop)]
+#[tauri::command]
+async fn install_update(app: tauri::AppHandle) -> Result<(), String> {
+    use tauri_plugin_process::ProcessExt;
+    use tauri_plugin_updater::UpdaterExt;
+
+    let update = app.updater().map_err(|e| e.to_string())?
+        .check().await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "no update available".to_string())?;
+
+    let mut downloaded: u64 = 0;
+    let update_bytes = update
+        .download(
+            |chunk_len, total| {
+                downloaded += chunk_len as u64;
+                let _ = app.emit("update://progress", UpdateProgressPayload { downloaded, total });
+            },
+            || {},
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // Kill sidecars only after the download succeeds, so a failed download
+    // doesn't leave every window's backend dead until the user relaunches.
+    for entry in app.state::<SidecarRegistry>().0.lock().unwrap().values() {
+        entry.generation.fetch_add(1, Ordering::SeqCst);
+        if let Some(child) = entry.child.lock().unwrap().take() {
+            let _ = child.kill();
+        }
+    }
+
+    update.install(update_bytes).map_err(|e| e.to_string())?;
+
+    let _ = app.emit("update://done", ());
+    app.restart();
 }
 
 #[tauri::command]
@@ -30,6 +458,8 @@ async fn open_project_in_new_window(
     app: tauri::AppHandle,
     project_id: String,
     project_name: String,
+    visible_on_all_workspaces: Option<bool>,
+    always_on_top: Option<bool>,
 ) -> Result<(), String> {
     let window_num = WINDOW_COUNTER.fetch_add(1, Ordering::SeqCst);
     let window_label = format!("project-{}", window_num);
@@ -37,17 +467,45 @@ async fn open_project_in_new_window(
     // Build URL with project ID as hash parameter
     let url = format!("index.html#/project/{}", project_id);
 
-    WebviewWindowBuilder::new(
+    let placement = app.state::<WindowPlacementStore>().0.lock().unwrap().get(&project_id).copied();
+    let placement = placement.filter(|p| placement_fits_a_monitor(&app, p));
+
+    let mut builder = WebviewWindowBuilder::new(
         &app,
         &window_label,
         WebviewUrl::App(url.into())
     )
     .title(format!("Navi - {}", project_name))
-    .inner_size(1200.0, 800.0)
     .min_inner_size(800.0, 600.0)
-    .center()
-    .build()
-    .map_err(|e| e.to_string())?;
+    .visible_on_all_workspaces(visible_on_all_workspaces.unwrap_or(false))
+    .always_on_top(always_on_top.unwrap_or(false));
+
+    builder = match placement {
+        Some(p) => builder.inner_size(p.width as f64, p.height as f64).position(p.x as f64, p.y as f64),
+        None => builder.inner_size(1200.0, 800.0).center(),
+    };
+
+    let window = builder.build().map_err(|e| e.to_string())?;
+
+    if placement.map(|p| p.maximized).unwrap_or(false) {
+        let _ = window.maximize();
+    }
+
+    // Save position/size/maximized state back to disk on move, resize, and close
+    // so the next time this project opens it restores here instead of centering.
+    let window_for_events = window.clone();
+    let app_for_events = app.clone();
+    let project_id_for_events = project_id.clone();
+    window.on_window_event(move |event| {
+        if matches!(
+            event,
+            tauri::WindowEvent::Moved(_) | tauri::WindowEvent::Resized(_) | tauri::WindowEvent::CloseRequested { .. }
+        ) {
+            persist_window_placement(&app_for_events, &project_id_for_events, &window_for_events);
+        }
+    });
+
+    spawn_window_sidecar(&app, window_label, Some(project_id));
 
     Ok(())
 }
@@ -65,55 +523,58 @@ pub fn run() {
         builder = builder.plugin(tauri_plugin_updater::Builder::new().build());
     }
 
-    builder
-        .manage(SidecarState(Mutex::new(None)))
-        .manage(ServerPorts { server: BUNDLED_SERVER_PORT, pty: BUNDLED_PTY_PORT })
+    let builder = builder
+        .manage(SidecarRegistry(Mutex::new(HashMap::new())))
         .setup(|app| {
-            let mut sidecar_command = app.shell().sidecar("navi-server").unwrap()
-                .args([BUNDLED_SERVER_PORT.to_string()]);
-
-            if let Ok(log_dir) = app.path().app_log_dir() {
-                sidecar_command = sidecar_command.env("NAVI_LOG_DIR", log_dir.to_string_lossy().to_string());
-            }
-
-            if let Ok(resource_dir) = app.path().resource_dir() {
-                sidecar_command = sidecar_command.env("TAURI_RESOURCE_DIR", resource_dir.to_string_lossy().to_string());
-                let cli_path = resource_dir.join("resources").join("claude-agent-sdk").join("cli.js");
-                sidecar_command = sidecar_command.env("NAVI_CLAUDE_CODE_PATH", cli_path.to_string_lossy().to_string());
+            let placements = load_window_placements(app.handle());
+            app.manage(WindowPlacementStore(Mutex::new(placements)));
 
-                if let Some(contents_dir) = resource_dir.parent() {
-                    let bun_path = contents_dir.join("MacOS").join("bun");
-                    sidecar_command = sidecar_command.env("NAVI_BUN_PATH", bun_path.to_string_lossy().to_string());
-                }
-            }
-            let (mut rx, child) = sidecar_command.spawn().expect("Failed to spawn sidecar");
-            
-            app.state::<SidecarState>().0.lock().unwrap().replace(child);
-            
-            tauri::async_runtime::spawn(async move {
-                while let Some(event) = rx.recv().await {
-                    match event {
-                        tauri_plugin_shell::process::CommandEvent::Stdout(line) => {
-                            println!("[sidecar stdout] {}", String::from_utf8_lossy(&line));
-                        }
-                        tauri_plugin_shell::process::CommandEvent::Stderr(line) => {
-                            eprintln!("[sidecar stderr] {}", String::from_utf8_lossy(&line));
-                        }
-                        _ => {}
-                    }
-                }
-            });
-            
+            spawn_window_sidecar(app.handle(), MAIN_WINDOW_LABEL.to_string(), None);
             Ok(())
         })
         .on_window_event(|window, event| {
             if let tauri::WindowEvent::Destroyed = event {
-                if let Some(child) = window.state::<SidecarState>().0.lock().unwrap().take() {
-                    let _ = child.kill();
+                let registry = window.state::<SidecarRegistry>();
+                let entry = registry.0.lock().unwrap().remove(window.label());
+                if let Some(entry) = entry {
+                    entry.generation.fetch_add(1, Ordering::SeqCst);
+                    if let Some(child) = entry.child.lock().unwrap().take() {
+                        let _ = child.kill();
+                    }
+                }
+
+                // Last window closed: defensively kill any sidecars left behind
+                // (there shouldn't be any, since each window cleans up its own).
+                if window.app_handle().webview_windows().is_empty() {
+                    let mut windows = registry.0.lock().unwrap();
+                    for (_, entry) in windows.drain() {
+                        entry.generation.fetch_add(1, Ordering::SeqCst);
+                        if let Some(child) = entry.child.lock().unwrap().take() {
+                            let _ = child.kill();
+                        }
+                    }
                 }
             }
-        })
-        .invoke_handler(tauri::generate_handler![greet, get_server_ports, open_project_in_new_window])
+        });
+
+    #[cfg(desktop)]
+    let builder = builder.invoke_handler(tauri::generate_handler![
+        greet,
+        get_server_ports,
+        get_log_path,
+        open_project_in_new_window,
+        check_for_update,
+        install_update
+    ]);
+    #[cfg(not(desktop))]
+    let builder = builder.invoke_handler(tauri::generate_handler![
+        greet,
+        get_server_ports,
+        get_log_path,
+        open_project_in_new_window
+    ]);
+
+    builder
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }